@@ -0,0 +1,41 @@
+// Copyright 2015-2018 Parity Technologies (UK) Ltd.
+// This file is part of Parity.
+
+// Parity is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Parity is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Parity.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Private transactions module errors.
+
+use ethereum_types::U256;
+
+error_chain! {
+	errors {
+		#[doc = "Queue of private transactions is full"]
+		QueueIsFull {
+			description("Private transactions queue is full"),
+			display("Private transactions queue is full"),
+		}
+
+		#[doc = "Private transaction is not found in the storage"]
+		PrivateTransactionNotFound {
+			description("Private transaction not found"),
+			display("Private transaction not found"),
+		}
+
+		#[doc = "Queued transaction's gas price is below the configured minimum"]
+		InsufficientGasPrice(minimal: U256, got: U256) {
+			description("Insufficient gas price"),
+			display("Private transaction's gas price is less than the minimal required: minimal {}, got {}", minimal, got),
+		}
+	}
+}
@@ -14,10 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with Parity.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::sync::Arc;
+use std::sync::{mpsc, Arc};
 use std::cmp;
-use std::collections::HashMap;
+use std::fmt;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::collections::hash_map::Entry;
+use std::time::{Duration, Instant};
 
 use bytes::Bytes;
 use ethcore_miner::pool;
@@ -25,19 +27,22 @@ use ethereum_types::{H256, U256, Address};
 use heapsize::HeapSizeOf;
 use ethkey::Signature;
 use messages::PrivateTransaction;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use transaction::{UnverifiedTransaction, SignedTransaction};
 use txpool;
 use txpool::{VerifiedTransaction, Verifier};
 use error::{Error, ErrorKind};
 
-type Pool = txpool::Pool<VerifiedPrivateTransaction, PrivateScorying>;
+type Pool<L> = txpool::Pool<VerifiedPrivateTransaction, PrivateScorying, L>;
 
 /// Maximum length for private transactions queues.
 const MAX_QUEUE_LEN: usize = 8312;
 /// Transaction with the same (sender, nonce) can be replaced only if
 /// `new_gas_price > old_gas_price + old_gas_price >> SHIFT`
 const GAS_PRICE_BUMP_SHIFT: usize = 3; // 2 = 25%, 3 = 12.5%, 4 = 6.25%
+/// Maximum time a transaction whose nonce is still in the future is allowed to sit in the
+/// verification pool before `VerificationStore::cull` removes it.
+const FUTURE_TX_TIMEOUT: Duration = Duration::from_secs(10 * 60);
 
 /// Desriptor for private transaction stored in queue for verification
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -72,7 +77,18 @@ impl txpool::VerifiedTransaction for VerifiedPrivateTransaction {
 }
 
 #[derive(Debug)]
-pub struct PrivateScorying;
+pub struct PrivateScorying {
+	minimal_gas_price: U256,
+}
+
+impl PrivateScorying {
+	/// Create new `PrivateScorying` enforcing the given minimal gas price floor.
+	pub fn new(minimal_gas_price: U256) -> Self {
+		PrivateScorying {
+			minimal_gas_price,
+		}
+	}
+}
 
 impl txpool::Scoring<VerifiedPrivateTransaction> for PrivateScorying {
 	type Score = U256;
@@ -108,7 +124,10 @@ impl txpool::Scoring<VerifiedPrivateTransaction> for PrivateScorying {
 				assert!(i < txs.len());
 				assert!(i < scores.len());
 
-				scores[i] = txs[i].transaction.transaction.gas_price;
+				// Score by the effective gas price: never let it fall below the
+				// configured floor, even if a transaction somehow bypassed `add_transaction`.
+				let gas_price = txs[i].transaction.transaction.gas_price;
+				scores[i] = cmp::max(gas_price, self.minimal_gas_price);
 			},
 			Change::Event(_) => {}
 		}
@@ -116,73 +135,341 @@ impl txpool::Scoring<VerifiedPrivateTransaction> for PrivateScorying {
 
 	fn should_replace(&self, old: &VerifiedPrivateTransaction, new: &VerifiedPrivateTransaction) -> bool {
 		if old.sender() == new.sender() {
-			// prefer earliest transaction
-			if new.transaction.nonce < old.transaction.nonce {
-				return true
-			}
+			// Within a single sender, the lowest nonce always wins: later nonces cannot
+			// become ready before it and would otherwise just waste a queue slot.
+			return new.transaction.nonce < old.transaction.nonce
+		}
+
+		// Across senders an absolute nonce comparison is meaningless (it says nothing about
+		// either account's readiness) and would risk evicting a ready transaction in favour of
+		// a future one just because its unrelated sender happens to be at a lower nonce. Score
+		// by gas price instead, mirroring `NonceAndGasPrice::should_replace`.
+		//
+		// Note this is still only a proxy for readiness, not the real thing: `Scoring` has no
+		// access to account state, so a high-gas-price future transaction can still outscore a
+		// ready one here. Avoiding that entirely would mean threading state into `Scoring`
+		// itself; until then, `PrivateReadyState`/`cull` are what keep a full queue usable by
+		// making sure ready transactions drain out promptly rather than relying on eviction.
+		new.transaction.gas_price > old.transaction.gas_price
+	}
+}
+
+/// Controls how many consecutive ready transactions `VerificationStore::drain_pending` accepts.
+#[derive(Debug, Clone)]
+pub struct PendingSettings {
+	/// Maximum number of consecutive ready nonces accepted from a single sender.
+	pub max_per_sender: usize,
+	/// Cumulative gas limit for the whole returned batch.
+	pub block_gas_limit: U256,
+}
+
+impl PendingSettings {
+	/// A single transaction per sender and no cumulative gas limit: the original, conservative
+	/// `drain` behaviour, safe when sequential private transactions can't be verified together.
+	pub fn single_transaction() -> Self {
+		PendingSettings {
+			max_per_sender: 1,
+			block_gas_limit: U256::max_value(),
 		}
+	}
+}
 
-		self.choose(old, new) == txpool::scoring::Choice::ReplaceOld
+impl Default for PendingSettings {
+	fn default() -> Self {
+		PendingSettings::single_transaction()
 	}
 }
 
 /// Checks readiness of transactions by comparing the nonce to state nonce.
-/// Guarantees only one transaction per sender
+/// By default guarantees only one transaction per sender; pass `PendingSettings` with a higher
+/// `max_per_sender` to allow a contiguous run of ready nonces from the same sender, stopping at
+/// the first gap, the first sender cap, or the cumulative `block_gas_limit`, whichever is first.
 #[derive(Debug)]
 pub struct PrivateReadyState<C> {
 	nonces: HashMap<Address, U256>,
+	counts: HashMap<Address, usize>,
+	gas_used: U256,
+	settings: PendingSettings,
 	state: C,
 }
 
 impl<C> PrivateReadyState<C> {
-	/// Create new State checker, given client interface.
+	/// Create new State checker, given client interface. Allows only one ready transaction
+	/// per sender; use `with_settings` to allow more.
 	pub fn new(
 		state: C,
 	) -> Self {
+		PrivateReadyState::with_settings(state, PendingSettings::single_transaction())
+	}
+
+	/// Create new State checker, given client interface and pending batch settings.
+	pub fn with_settings(state: C, settings: PendingSettings) -> Self {
 		PrivateReadyState {
 			nonces: Default::default(),
+			counts: Default::default(),
+			gas_used: 0.into(),
+			settings,
 			state,
 		}
 	}
 }
 
+impl<C: pool::client::NonceClient> PrivateReadyState<C> {
+	/// Computes the readiness of a transaction at `nonce` for `sender`, caching the account's
+	/// current state nonce the first time the sender is seen. Only that first check can come
+	/// back `Stale`; a later one is `Ready` if its nonce equals the cached nonce (which every
+	/// `Ready` result advances by one) and `Future` otherwise.
+	fn check(&mut self, sender: &Address, nonce: &U256) -> txpool::Readiness {
+		let state = &self.state;
+		match self.nonces.entry(*sender) {
+			Entry::Vacant(entry) => {
+				let state_nonce = state.account_nonce(sender);
+				let cached = entry.insert(state_nonce);
+				match nonce.cmp(cached) {
+					cmp::Ordering::Greater => txpool::Readiness::Future,
+					cmp::Ordering::Less => txpool::Readiness::Stale,
+					cmp::Ordering::Equal => {
+						*cached = *cached + 1.into();
+						txpool::Readiness::Ready
+					},
+				}
+			}
+			Entry::Occupied(mut entry) => {
+				// A sender can legitimately have more than one queued transaction: the
+				// immediate successor of an already-resolved nonce is still `Ready`, not
+				// `Future`, so a contiguous run isn't mistaken for a gap and TTL-culled.
+				// Only an actual gap (anything but the next consecutive nonce) is `Future`.
+				let cached = entry.get_mut();
+				if nonce == cached {
+					*cached = *cached + 1.into();
+					txpool::Readiness::Ready
+				} else {
+					txpool::Readiness::Future
+				}
+			}
+		}
+	}
+}
+
 impl<C: pool::client::NonceClient> txpool::Ready<VerifiedPrivateTransaction> for PrivateReadyState<C> {
 	fn is_ready(&mut self, tx: &VerifiedPrivateTransaction) -> txpool::Readiness {
-		let sender = tx.sender();
+		let sender = *tx.sender();
+		let nonce = tx.transaction.nonce;
+
+		if self.gas_used + tx.transaction.gas > self.settings.block_gas_limit {
+			return txpool::Readiness::Future
+		}
+		if self.counts.get(&sender).cloned().unwrap_or(0) >= self.settings.max_per_sender {
+			return txpool::Readiness::Future
+		}
+
 		let state = &self.state;
-		let state_nonce = state.account_nonce(sender);
-		match self.nonces.entry(*sender) {
+		let readiness = match self.nonces.entry(sender) {
 			Entry::Vacant(entry) => {
-				let nonce = entry.insert(state_nonce);
-				match tx.transaction.nonce.cmp(nonce) {
+				let state_nonce = state.account_nonce(&sender);
+				let cached = entry.insert(state_nonce);
+				match nonce.cmp(cached) {
 					cmp::Ordering::Greater => txpool::Readiness::Future,
 					cmp::Ordering::Less => txpool::Readiness::Stale,
 					cmp::Ordering::Equal => {
-						*nonce = *nonce + 1.into();
+						*cached = *cached + 1.into();
 						txpool::Readiness::Ready
 					},
 				}
 			}
-			Entry::Occupied(_) => {
-				txpool::Readiness::Future
+			Entry::Occupied(mut entry) => {
+				// Allow the next consecutive nonce to also be ready; anything else (a gap, or a
+				// nonce from before the cached one) stops the run for this sender.
+				let cached = entry.get_mut();
+				if nonce == *cached {
+					*cached = *cached + 1.into();
+					txpool::Readiness::Ready
+				} else {
+					txpool::Readiness::Future
+				}
+			}
+		};
+
+		if readiness == txpool::Readiness::Ready {
+			*self.counts.entry(sender).or_insert(0) += 1;
+			self.gas_used = self.gas_used + tx.transaction.gas;
+		}
+
+		readiness
+	}
+}
+
+/// Readiness check used by `cull`: a transaction that's genuinely `Stale` stays `Stale`, but a
+/// `Future` one is promoted to `Stale` too once it's been sitting in `insertion_times` longer
+/// than `FUTURE_TX_TIMEOUT`, so `pool.cull` removes (and reports `culled` for) both in one pass.
+struct CullReadyState<C> {
+	inner: PrivateReadyState<C>,
+	insertion_times: HashMap<H256, QueuedAt>,
+	now: Instant,
+}
+
+impl<C: pool::client::NonceClient> txpool::Ready<VerifiedPrivateTransaction> for CullReadyState<C> {
+	fn is_ready(&mut self, tx: &VerifiedPrivateTransaction) -> txpool::Readiness {
+		match self.inner.check(tx.sender(), &tx.transaction.nonce) {
+			txpool::Readiness::Future => {
+				let expired = self.insertion_times.get(tx.hash())
+					.map_or(false, |queued| self.now.duration_since(queued.inserted) > FUTURE_TX_TIMEOUT);
+				if expired { txpool::Readiness::Stale } else { txpool::Readiness::Future }
 			}
+			readiness => readiness,
 		}
 	}
 }
 
+/// Bookkeeping kept alongside a queued transaction so `cull` can tell how long a `Future`
+/// transaction has been sitting in the pool without re-deriving it from `insertion_times`'s key.
+#[derive(Debug, Clone)]
+struct QueuedAt {
+	inserted: Instant,
+}
+
+/// Lifecycle events reported by `PrivateTxListener` for a transaction in the verification pool.
+#[derive(Debug, Clone)]
+pub enum PrivateTxEvent {
+	/// Transaction was imported into the verification pool, replacing `old` if it was set.
+	Added(Arc<VerifiedPrivateTransaction>, Option<Arc<VerifiedPrivateTransaction>>),
+	/// Transaction was rejected on import; `reason` describes why.
+	Rejected(Arc<VerifiedPrivateTransaction>, String),
+	/// Transaction was dropped from the pool to make room for another.
+	Dropped(Arc<VerifiedPrivateTransaction>),
+	/// Transaction turned out to be invalid once verified.
+	Invalid(Arc<VerifiedPrivateTransaction>),
+	/// Transaction was removed from the pool without being invalid, e.g. drained for verification.
+	Canceled(Arc<VerifiedPrivateTransaction>),
+	/// Transaction was culled from the pool for being stale or an expired future transaction.
+	Culled(Arc<VerifiedPrivateTransaction>),
+}
+
+/// Forwards private transaction pool lifecycle events to registered subscribers, so RPC and
+/// notification layers can observe import/replace/cull activity on the private-tx queue and,
+/// for example, kick off signing once a transaction is verified.
+#[derive(Default)]
+pub struct PrivateTxListener {
+	subscribers: RwLock<Vec<mpsc::Sender<PrivateTxEvent>>>,
+}
+
+impl PrivateTxListener {
+	/// Registers a new subscriber, returning the receiving end of a channel on which it will
+	/// observe all subsequent events. Subscribers whose receiver has been dropped are pruned
+	/// the next time an event is sent.
+	pub fn subscribe(&self) -> mpsc::Receiver<PrivateTxEvent> {
+		let (sender, receiver) = mpsc::channel();
+		self.subscribers.write().push(sender);
+		receiver
+	}
+
+	fn notify(&self, event: PrivateTxEvent) {
+		self.subscribers.write().retain(|subscriber| subscriber.send(event.clone()).is_ok());
+	}
+}
+
+impl txpool::Listener<VerifiedPrivateTransaction> for PrivateTxListener {
+	fn added(&mut self, tx: &Arc<VerifiedPrivateTransaction>, old: Option<&Arc<VerifiedPrivateTransaction>>) {
+		self.notify(PrivateTxEvent::Added(tx.clone(), old.cloned()));
+	}
+
+	fn rejected<R: fmt::Debug>(&mut self, tx: &Arc<VerifiedPrivateTransaction>, reason: &R) {
+		self.notify(PrivateTxEvent::Rejected(tx.clone(), format!("{:?}", reason)));
+	}
+
+	fn dropped(&mut self, tx: &Arc<VerifiedPrivateTransaction>, _by: Option<&VerifiedPrivateTransaction>) {
+		self.notify(PrivateTxEvent::Dropped(tx.clone()));
+	}
+
+	fn invalid(&mut self, tx: &Arc<VerifiedPrivateTransaction>) {
+		self.notify(PrivateTxEvent::Invalid(tx.clone()));
+	}
+
+	fn canceled(&mut self, tx: &Arc<VerifiedPrivateTransaction>) {
+		self.notify(PrivateTxEvent::Canceled(tx.clone()));
+	}
+
+	fn culled(&mut self, tx: &Arc<VerifiedPrivateTransaction>) {
+		self.notify(PrivateTxEvent::Culled(tx.clone()));
+	}
+}
+
+/// Installed as the verification pool's actual listener. Keeps `insertion_times` in lock-step
+/// with pool membership by pruning (or, for `added`, refreshing) the relevant entry on every
+/// event the pool itself fires, then forwards the event unchanged to `inner`. Doing this here,
+/// rather than from call sites in `VerificationStore`, means `insertion_times` can never drift
+/// out of sync with a replacement/drop/cull the pool decided on internally, and every removal
+/// path reports exactly the event the pool chose (no separate code path can double-report it).
+struct CombinedListener<L> {
+	insertion_times: Arc<RwLock<HashMap<H256, QueuedAt>>>,
+	inner: L,
+}
+
+impl<L: txpool::Listener<VerifiedPrivateTransaction>> txpool::Listener<VerifiedPrivateTransaction> for CombinedListener<L> {
+	fn added(&mut self, tx: &Arc<VerifiedPrivateTransaction>, old: Option<&Arc<VerifiedPrivateTransaction>>) {
+		{
+			let mut insertion_times = self.insertion_times.write();
+			if let Some(old) = old {
+				insertion_times.remove(old.hash());
+			}
+			insertion_times.insert(*tx.hash(), QueuedAt { inserted: Instant::now() });
+		}
+		self.inner.added(tx, old);
+	}
+
+	fn rejected<R: fmt::Debug>(&mut self, tx: &Arc<VerifiedPrivateTransaction>, reason: &R) {
+		self.inner.rejected(tx, reason);
+	}
+
+	fn dropped(&mut self, tx: &Arc<VerifiedPrivateTransaction>, by: Option<&VerifiedPrivateTransaction>) {
+		self.insertion_times.write().remove(tx.hash());
+		self.inner.dropped(tx, by);
+	}
+
+	fn invalid(&mut self, tx: &Arc<VerifiedPrivateTransaction>) {
+		self.insertion_times.write().remove(tx.hash());
+		self.inner.invalid(tx);
+	}
+
+	fn canceled(&mut self, tx: &Arc<VerifiedPrivateTransaction>) {
+		self.insertion_times.write().remove(tx.hash());
+		self.inner.canceled(tx);
+	}
+
+	fn culled(&mut self, tx: &Arc<VerifiedPrivateTransaction>) {
+		self.insertion_times.write().remove(tx.hash());
+		self.inner.culled(tx);
+	}
+}
+
 /// Storage for private transactions for verification
-pub struct VerificationStore {
-	verification_pool: RwLock<Pool>,
+pub struct VerificationStore<L = PrivateTxListener> {
+	verification_pool: RwLock<Pool<CombinedListener<L>>>,
 	verification_options: pool::verifier::Options,
+	insertion_times: Arc<RwLock<HashMap<H256, QueuedAt>>>,
 }
 
-impl Default for VerificationStore {
+impl Default for VerificationStore<PrivateTxListener> {
 	fn default() -> Self {
+		VerificationStore::new(pool::verifier::Options {
+			minimal_gas_price: 0.into(),
+			block_gas_limit: 8_000_000.into(),
+			tx_gas_limit: U256::max_value(),
+		}, PrivateTxListener::default())
+	}
+}
+
+impl<L: txpool::Listener<VerifiedPrivateTransaction>> VerificationStore<L> {
+	/// Creates a new verification store, using the given verifier options to configure
+	/// the `minimal_gas_price`, `block_gas_limit` and `tx_gas_limit` applied to transactions
+	/// entering the private pool, and reporting lifecycle events to `listener`.
+	pub fn new(verification_options: pool::verifier::Options, listener: L) -> Self {
+		let insertion_times: Arc<RwLock<HashMap<H256, QueuedAt>>> = Arc::new(RwLock::new(HashMap::new()));
 		VerificationStore {
 			verification_pool: RwLock::new(
 				txpool::Pool::new(
-					txpool::NoopListener,
-					PrivateScorying,
+					CombinedListener { insertion_times: insertion_times.clone(), inner: listener },
+					PrivateScorying::new(verification_options.minimal_gas_price),
 					pool::Options {
 						max_count: MAX_QUEUE_LEN,
 						max_per_sender: MAX_QUEUE_LEN / 10,
@@ -190,17 +477,11 @@ impl Default for VerificationStore {
 					},
 				)
 			),
-			verification_options: pool::verifier::Options {
-				// TODO [ToDr] This should probably be based on some real values?
-				minimal_gas_price: 0.into(),
-				block_gas_limit: 8_000_000.into(),
-				tx_gas_limit: U256::max_value(),
-			},
+			verification_options,
+			insertion_times,
 		}
 	}
-}
 
-impl VerificationStore {
 	/// Adds private transaction for verification into the store
 	pub fn add_transaction<C: pool::client::Client>(
 		&self,
@@ -209,19 +490,26 @@ impl VerificationStore {
 		private_transaction: PrivateTransaction,
 		client: C,
 	) -> Result<(), Error> {
+		if transaction.gas_price < self.verification_options.minimal_gas_price {
+			bail!(ErrorKind::InsufficientGasPrice(self.verification_options.minimal_gas_price, transaction.gas_price));
+		}
 
 		let options = self.verification_options.clone();
 		// Use pool's verifying pipeline for original transaction's verification
 		let verifier = pool::verifier::Verifier::new(client, options, Default::default());
 		let _verified_tx = verifier.verify_transaction(pool::verifier::Transaction::Unverified(transaction.clone()))?;
 		let signed_tx = SignedTransaction::new(transaction)?;
+		let sender = signed_tx.sender();
+		let hash = signed_tx.hash();
 		let verified = VerifiedPrivateTransaction {
 			private_transaction,
 			validator_account,
 			transaction: signed_tx.clone(),
-			transaction_hash: signed_tx.hash(),
-			transaction_sender: signed_tx.sender(),
+			transaction_hash: hash,
+			transaction_sender: sender,
 		};
+		// `insertion_times` is populated by `CombinedListener::added`, fired from within
+		// `import` itself, so it can never drift out of sync with what actually made it in.
 		let mut pool = self.verification_pool.write();
 		pool.import(verified)?;
 		Ok(())
@@ -230,24 +518,45 @@ impl VerificationStore {
 	/// Drains transactions ready for verification from the pool
 	/// Returns only one transaction per sender because several cannot be verified in a row without verification from other peers
 	pub fn drain<C: pool::client::NonceClient>(&self, client: C) -> Vec<Arc<VerifiedPrivateTransaction>> {
-		let ready = PrivateReadyState::new(client);
-		let mut hashes: Vec<H256> = Vec::new();
+		self.drain_pending(client, PendingSettings::single_transaction())
+	}
+
+	/// Drains transactions ready for verification from the pool, using `settings` to control how
+	/// many consecutive ready nonces per sender (and how much cumulative gas) a single drain may
+	/// return. Stops at the first nonce gap for a sender, yielding a contiguous ready batch
+	/// suitable for constructing a private block.
+	pub fn drain_pending<C: pool::client::NonceClient>(&self, client: C, settings: PendingSettings) -> Vec<Arc<VerifiedPrivateTransaction>> {
+		let ready = PrivateReadyState::with_settings(client, settings);
 		let res: Vec<Arc<VerifiedPrivateTransaction>> = self.verification_pool.read().pending(ready).collect();
-		res
-			.iter()
-			.for_each(|tx| {
-				hashes.push(tx.hash().clone());
-			}
-		);
+		// `remove(_, false)` reports each transaction as `canceled` (not `invalid`) through the
+		// pool's own listener, which also prunes `insertion_times` - nothing else to do here.
 		let mut pool = self.verification_pool.write();
-		hashes
-			.iter()
-			.for_each(|hash| {
-				pool.remove(&hash, true);
-			}
-		);
+		for tx in &res {
+			pool.remove(tx.hash(), false);
+		}
 		res
 	}
+
+	/// Culls stale and long-standing future transactions from the verification pool.
+	///
+	/// A transaction becomes "stale" once `client`'s nonce for its sender has moved past it,
+	/// and a "future" transaction (nonce still ahead of the sender's current nonce) is culled
+	/// once it has been queued for longer than `FUTURE_TX_TIMEOUT`. Without this, a sender that
+	/// publishes a nonce gap that never fills in would occupy a queue slot forever. Safe to call
+	/// periodically from the service loop.
+	pub fn cull<C: pool::client::NonceClient>(&self, client: C) {
+		// Snapshot insertion times up front rather than holding the lock: `pool.cull` fires
+		// `culled` synchronously through `CombinedListener`, which takes a write lock on this
+		// same map to prune each removed entry, so holding our own read lock across the call
+		// would deadlock against it.
+		let insertion_times = self.insertion_times.read().clone();
+		let ready = CullReadyState {
+			inner: PrivateReadyState::new(client),
+			insertion_times,
+			now: Instant::now(),
+		};
+		self.verification_pool.write().cull(None, ready);
+	}
 }
 
 /// Desriptor for private transaction stored in queue for signing
@@ -255,39 +564,186 @@ impl VerificationStore {
 pub struct PrivateTransactionSigningDesc {
 	/// Original unsigned transaction
 	pub original_transaction: SignedTransaction,
+	/// Private contract this transaction is signed against
+	pub contract: Address,
 	/// Supposed validators from the contract
 	pub validators: Vec<Address>,
 	/// Already obtained signatures
 	pub received_signatures: Vec<Signature>,
 	/// State after transaction execution to compare further with received from validators
 	pub state: Bytes,
-	/// Build-in nonce of the contract
+	/// Build-in nonce of the contract, claimed via a `Reservation` so no two transactions
+	/// queued for the same contract can ever be signed against the same nonce
 	pub contract_nonce: U256,
 }
 
-/// Storage for private transactions for signing
+/// Per-contract nonce bookkeeping backing `SigningStore::reserve_nonce`.
 #[derive(Default)]
+struct NonceReservations {
+	/// Next never-yet-handed-out nonce for each contract.
+	next: HashMap<Address, U256>,
+	/// Nonces reserved and then dropped before being dispatched; free to hand out again.
+	released: HashMap<Address, BTreeSet<U256>>,
+	/// Nonces that have been dispatched, i.e. actually claimed by a queued transaction.
+	dispatched: HashMap<Address, HashSet<U256>>,
+}
+
+impl NonceReservations {
+	/// Reserves the next free nonce for `contract`, seeded from `chain_nonce` (the contract's
+	/// real, on-chain nonce) so the very first reservation for a contract doesn't start from
+	/// zero.
+	fn reserve(&mut self, contract: Address, chain_nonce: U256) -> U256 {
+		// Bring the counter up to the contract's real nonce, but never rewind it: once we've
+		// started handing out nonces above `chain_nonce` we must keep counting up from there,
+		// even if called again with a stale seed.
+		{
+			let next = self.next.entry(contract).or_insert(chain_nonce);
+			if chain_nonce > *next {
+				*next = chain_nonce;
+			}
+		}
+
+		loop {
+			// Prefer recycling a released nonce over minting a new one: it was reserved and
+			// then abandoned without ever being dispatched, so it's free to reuse. Drop the
+			// stale ones (below the chain nonce) and pick the smallest remaining one, so released
+			// nonces don't pile up unboundedly; this is safe because `release` only ever runs for a
+			// `Reservation` that was never `dispatch`-ed, so a recycled nonce can't collide
+			// with one a validator has already signed against.
+			let recycled = self.released.get_mut(&contract).and_then(|released| {
+				*released = released.split_off(&chain_nonce);
+				let nonce = released.iter().next().cloned();
+				if let Some(nonce) = nonce {
+					released.remove(&nonce);
+				}
+				nonce
+			});
+
+			let candidate = match recycled {
+				Some(nonce) => nonce,
+				None => {
+					let next = self.next.get_mut(&contract).expect("seeded above");
+					let nonce = *next;
+					*next = nonce + U256::one();
+					nonce
+				}
+			};
+
+			// Should be unreachable given `release` and `dispatch` are mutually exclusive for
+			// a given nonce, but never hand out one that's already in flight.
+			let in_flight = self.dispatched.get(&contract).map_or(false, |set| set.contains(&candidate));
+			if !in_flight {
+				return candidate;
+			}
+		}
+	}
+
+	fn release(&mut self, contract: Address, nonce: U256) {
+		self.released.entry(contract).or_insert_with(BTreeSet::new).insert(nonce);
+	}
+
+	fn dispatch(&mut self, contract: Address, nonce: U256) {
+		self.dispatched.entry(contract).or_insert_with(HashSet::new).insert(nonce);
+	}
+
+	fn forget(&mut self, contract: Address, nonce: U256) {
+		if let Some(dispatched) = self.dispatched.get_mut(&contract) {
+			dispatched.remove(&nonce);
+		}
+	}
+}
+
+/// A nonce reserved for a private contract by `SigningStore::reserve_nonce`.
+///
+/// Holding a `Reservation` guarantees no other caller can claim the same contract nonce.
+/// Dropping it before it is claimed by `SigningStore::add_transaction` returns the nonce to
+/// the pool so it can be handed out again.
+pub struct Reservation {
+	contract: Address,
+	nonce: U256,
+	reservations: Arc<Mutex<NonceReservations>>,
+	dispatched: bool,
+}
+
+impl Reservation {
+	/// The reserved nonce.
+	pub fn nonce(&self) -> U256 {
+		self.nonce
+	}
+
+	/// The contract this nonce was reserved for.
+	pub fn contract(&self) -> Address {
+		self.contract
+	}
+
+	/// Claims the reservation, marking the nonce as dispatched so it is never handed out again.
+	fn dispatch(mut self) -> U256 {
+		self.dispatched = true;
+		self.reservations.lock().dispatch(self.contract, self.nonce);
+		self.nonce
+	}
+}
+
+impl Drop for Reservation {
+	fn drop(&mut self) {
+		if !self.dispatched {
+			self.reservations.lock().release(self.contract, self.nonce);
+		}
+	}
+}
+
+/// Storage for private transactions for signing
 pub struct SigningStore {
 	/// Transactions and descriptors for signing
 	transactions: HashMap<H256, PrivateTransactionSigningDesc>,
+	/// Nonces reserved against each contract currently being signed for.
+	reservations: Arc<Mutex<NonceReservations>>,
+}
+
+impl Default for SigningStore {
+	fn default() -> Self {
+		SigningStore {
+			transactions: HashMap::new(),
+			reservations: Arc::new(Mutex::new(NonceReservations::default())),
+		}
+	}
 }
 
 impl SigningStore {
-	/// Adds new private transaction into the store for signing
+	/// Reserves the next free nonce for `contract`, seeded from `nonce` (the contract's current
+	/// on-chain nonce), so reservations are always handed out from the contract's real position
+	/// rather than an independent per-process counter. Hold on to the returned `Reservation`
+	/// until the transaction it backs is either queued via `add_transaction` or abandoned;
+	/// dropping it unclaimed returns the nonce to the pool.
+	pub fn reserve_nonce(&self, contract: Address, nonce: U256) -> Reservation {
+		let nonce = self.reservations.lock().reserve(contract, nonce);
+		Reservation {
+			contract,
+			nonce,
+			reservations: self.reservations.clone(),
+			dispatched: false,
+		}
+	}
+
+	/// Adds new private transaction into the store for signing, claiming `reservation`'s nonce
+	/// as the contract nonce rather than trusting one supplied directly by the caller.
 	pub fn add_transaction(
 		&mut self,
 		private_hash: H256,
 		transaction: SignedTransaction,
 		validators: Vec<Address>,
 		state: Bytes,
-		contract_nonce: U256,
+		reservation: Reservation,
 	) -> Result<(), Error> {
 		if self.transactions.len() > MAX_QUEUE_LEN {
 			bail!(ErrorKind::QueueIsFull);
 		}
 
+		let contract = reservation.contract();
+		let contract_nonce = reservation.dispatch();
 		self.transactions.insert(private_hash, PrivateTransactionSigningDesc {
 			original_transaction: transaction.clone(),
+			contract,
 			validators: validators.clone(),
 			received_signatures: Vec::new(),
 			state,
@@ -301,9 +757,12 @@ impl SigningStore {
 		self.transactions.get(private_hash).cloned()
 	}
 
-	/// Removes desc from the store (after verification is completed)
+	/// Removes desc from the store (after verification is completed), freeing its reservation
+	/// bookkeeping
 	pub fn remove(&mut self, private_hash: &H256) -> Result<(), Error> {
-		self.transactions.remove(private_hash);
+		if let Some(desc) = self.transactions.remove(private_hash) {
+			self.reservations.lock().forget(desc.contract, desc.contract_nonce);
+		}
 		Ok(())
 	}
 